@@ -1,4 +1,8 @@
-use std::{collections::VecDeque, io::BufRead};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, VecDeque},
+    io::BufRead,
+};
 
 type AnyResult<T> = Result<T, ()>;
 
@@ -61,7 +65,7 @@ type AnyResult<T> = Result<T, ()>;
 //
 // Here, each level of the tree would be the cost (distance).
 // (Note that 5 could be attached to 6 instead)
-fn solve(shortcuts: &[usize]) -> impl Iterator<Item = usize> {
+fn solve(shortcuts: &[Vec<usize>]) -> impl Iterator<Item = usize> {
     // If we found a better distance (meaning the current_dist is better than
     // the one we found in the given position), update the dist and push it to
     // the working queue.
@@ -78,14 +82,16 @@ fn solve(shortcuts: &[usize]) -> impl Iterator<Item = usize> {
     }
 
     // Just to have 0-base indexing.
-    let shortcuts = shortcuts.iter().map(|elt| elt - 1).collect::<Vec<_>>();
+    let shortcuts = shortcuts
+        .iter()
+        .map(|targets| targets.iter().map(|elt| elt - 1).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
     let mut queue = VecDeque::<usize>::new();
     let mut distances = vec![None; shortcuts.len()];
     distances[0] = Some(0);
 
     queue.push_front(0);
     while let Some(current) = queue.pop_front() {
-        let shortcut = shortcuts[current];
         let prev = (current > 0).then(|| current - 1);
         let next = (current < shortcuts.len() - 1).then(|| current + 1);
         let current_dist = distances[current].map_or(0, |current_dist| 1 + current_dist);
@@ -96,24 +102,538 @@ fn solve(shortcuts: &[usize]) -> impl Iterator<Item = usize> {
         if let Some(next) = next {
             update_and_push_if_better(&mut queue, &mut distances, next, current_dist);
         }
-        update_and_push_if_better(&mut queue, &mut distances, shortcut, current_dist);
+        for &shortcut in &shortcuts[current] {
+            update_and_push_if_better(&mut queue, &mut distances, shortcut, current_dist);
+        }
     }
     distances.into_iter().flatten() // Assuming here there isn't any None.
 }
 
-fn main() -> AnyResult<()> {
-    let mut lines = std::io::stdin().lock().lines().skip(1);
-    let line = lines.next().unwrap().map_err(|_| ())?;
-    let split = line
-        .split(' ')
-        .map(|ch| ch.parse().expect("no issue"))
+// Parses lines of the form `node: t1 t2 t3 ...`, one per node, where `node`
+// and every `tN` are the same 1-based indices `solve` expects — a node with
+// no shortcuts can simply be omitted, which is why `node_count` is required
+// upfront rather than inferred from the highest `node` seen: it's the only
+// way to know the adjacency list's true length when trailing nodes have no
+// line at all. Returns the adjacency list indexed by 0-based node, always
+// exactly `node_count` long, ready to hand to `solve`.
+fn parse_graph<I: IntoIterator<Item = String>>(node_count: usize, lines: I) -> Vec<Vec<usize>> {
+    let mut shortcuts: Vec<Vec<usize>> = vec![Vec::new(); node_count];
+    for line in lines {
+        let Some((node, targets)) = line.split_once(':') else {
+            continue;
+        };
+        let node = node.trim().parse::<usize>().expect("no issue");
+        let targets = targets
+            .split_whitespace()
+            .map(|target| target.parse().expect("no issue"))
+            .collect::<Vec<_>>();
+
+        if shortcuts.len() < node {
+            shortcuts.resize(node, Vec::new());
+        }
+        shortcuts[node - 1] = targets;
+    }
+    shortcuts
+}
+
+// A single hop in a reconstructed path, tagged with the kind of edge that was
+// taken to reach the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Move {
+    Left,
+    Right,
+    Shortcut,
+}
+
+// Same traversal as `solve`, but additionally keeps a `predecessors` table so
+// the shortest route to any node can be rebuilt afterward, not just its
+// distance.
+//
+// `predecessors[node]` holds the node we came from together with the move
+// that got us there, and is only overwritten when a *strictly* better
+// distance is found for `node` (mirroring `update_and_push_if_better`'s
+// condition), so ties always keep the first predecessor recorded.
+//
+// Returns, for every node, its distance from 0 plus the sequence of node
+// indices and moves making up the shortest path to it.
+fn solve_with_paths(shortcuts: &[Vec<usize>]) -> Vec<(usize, Vec<usize>, Vec<Move>)> {
+    fn update_and_push_if_better(
+        queue: &mut VecDeque<usize>,
+        distances: &mut [Option<usize>],
+        predecessors: &mut [Option<(usize, Move)>],
+        current: usize,
+        other: usize,
+        current_dist: usize,
+        mv: Move,
+    ) {
+        if distances[other].map_or(true, |distance_other| current_dist < distance_other) {
+            distances[other] = Some(current_dist);
+            predecessors[other] = Some((current, mv));
+            queue.push_front(other);
+        }
+    }
+
+    // Just to have 0-base indexing.
+    let shortcuts = shortcuts
+        .iter()
+        .map(|targets| targets.iter().map(|elt| elt - 1).collect::<Vec<_>>())
         .collect::<Vec<_>>();
+    let mut queue = VecDeque::<usize>::new();
+    let mut distances = vec![None; shortcuts.len()];
+    let mut predecessors = vec![None; shortcuts.len()];
+    distances[0] = Some(0);
 
-    let res = solve(&split).collect::<Vec<_>>();
-    for elt in res {
-        print!("{} ", elt);
+    queue.push_front(0);
+    while let Some(current) = queue.pop_front() {
+        let prev = (current > 0).then(|| current - 1);
+        let next = (current < shortcuts.len() - 1).then(|| current + 1);
+        let current_dist = distances[current].map_or(0, |current_dist| 1 + current_dist);
+
+        if let Some(prev) = prev {
+            update_and_push_if_better(
+                &mut queue,
+                &mut distances,
+                &mut predecessors,
+                current,
+                prev,
+                current_dist,
+                Move::Left,
+            );
+        }
+        if let Some(next) = next {
+            update_and_push_if_better(
+                &mut queue,
+                &mut distances,
+                &mut predecessors,
+                current,
+                next,
+                current_dist,
+                Move::Right,
+            );
+        }
+        for &shortcut in &shortcuts[current] {
+            update_and_push_if_better(
+                &mut queue,
+                &mut distances,
+                &mut predecessors,
+                current,
+                shortcut,
+                current_dist,
+                Move::Shortcut,
+            );
+        }
+    }
+
+    // Walk predecessors back to 0 for every node, then reverse to get a
+    // route that reads from 0 to the target.
+    (0..shortcuts.len())
+        .map(|target| {
+            let distance = distances[target].expect("every node is reachable from 0");
+            let mut path = vec![target];
+            let mut moves = Vec::new();
+            let mut node = target;
+            while let Some((pred, mv)) = predecessors[node] {
+                path.push(pred);
+                moves.push(mv);
+                node = pred;
+            }
+            path.reverse();
+            moves.reverse();
+            (distance, path, moves)
+        })
+        .collect()
+}
+
+// A `BinaryHeap` entry for `solve_weighted`. `BinaryHeap` is a max-heap, so
+// `Ord` reverses the comparison on `cost` to turn it into a min-heap;
+// `position` is only there to make the ordering total.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct State {
+    cost: usize,
+    position: usize,
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .cmp(&self.cost)
+            .then_with(|| self.position.cmp(&other.position))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Dijkstra over the same left/right/shortcut graph as `solve`, but with
+// caller-supplied costs for a normal step and for taking a shortcut. Unlike
+// the unit-cost BFS, distances are no longer monotonic with traversal order,
+// so nodes are expanded from a min-heap by current best cost instead of a
+// `VecDeque`, popping stale entries (whose recorded cost no longer matches
+// the best known distance) without processing them again.
+fn solve_weighted(shortcuts: &[Vec<usize>], step_cost: usize, shortcut_cost: usize) -> Vec<usize> {
+    // Just to have 0-base indexing.
+    let shortcuts = shortcuts
+        .iter()
+        .map(|targets| targets.iter().map(|elt| elt - 1).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let mut distances = vec![usize::MAX; shortcuts.len()];
+    let mut heap = BinaryHeap::new();
+
+    distances[0] = 0;
+    heap.push(State {
+        cost: 0,
+        position: 0,
+    });
+
+    while let Some(State { cost, position }) = heap.pop() {
+        if cost > distances[position] {
+            continue; // Stale entry: a cheaper path was already found.
+        }
+
+        let prev = (position > 0).then(|| position - 1);
+        let next = (position < shortcuts.len() - 1).then(|| position + 1);
+
+        let mut relax = |other: usize, edge_cost: usize| {
+            let next_cost = cost + edge_cost;
+            if next_cost < distances[other] {
+                distances[other] = next_cost;
+                heap.push(State {
+                    cost: next_cost,
+                    position: other,
+                });
+            }
+        };
+
+        if let Some(prev) = prev {
+            relax(prev, step_cost);
+        }
+        if let Some(next) = next {
+            relax(next, step_cost);
+        }
+        for &shortcut in &shortcuts[position] {
+            relax(shortcut, shortcut_cost);
+        }
+    }
+
+    distances
+}
+
+// Same unit-cost relaxation as `solve`, but with an SLF+LLL queue discipline
+// (Small-Label-First / Large-Label-Last) instead of always `push_front`ing.
+// On adversarial shortcut layouts, plain SPFA can re-expand the same node
+// many times; ordering the deque so cheap nodes come out first cuts that
+// down while still producing identical distances.
+//
+// - SLF: a node whose distance improves is pushed to the front of the deque
+//   if it's cheaper than the node currently at the front, and to the back
+//   otherwise, so cheap nodes bubble toward the head.
+// - LLL: before expanding the front of the deque, it's rotated to the back
+//   (and the new front tried instead) for as long as it's pricier than the
+//   average distance of everything currently queued.
+// - `in_queue` avoids enqueuing a node twice; if it improves again while
+//   already queued, only the running sum used for LLL's average is updated,
+//   not the deque itself.
+fn solve_slf_lll(shortcuts: &[Vec<usize>]) -> Vec<usize> {
+    fn update_and_push_if_better(
+        queue: &mut VecDeque<usize>,
+        distances: &mut [Option<usize>],
+        in_queue: &mut [bool],
+        sum: &mut usize,
+        count: &mut usize,
+        other: usize,
+        current_dist: usize,
+    ) {
+        if distances[other].map_or(true, |distance_other| current_dist < distance_other) {
+            let old_dist = distances[other];
+            distances[other] = Some(current_dist);
+
+            if in_queue[other] {
+                // Already queued: keep the running sum in sync, no need to
+                // touch the deque itself.
+                *sum = *sum + current_dist - old_dist.unwrap_or(0);
+                return;
+            }
+
+            in_queue[other] = true;
+            *sum += current_dist;
+            *count += 1;
+
+            // SLF: cheaper than the front goes to the front, otherwise back.
+            let goes_front = queue
+                .front()
+                .and_then(|&front| distances[front])
+                .map_or(true, |front_dist| current_dist < front_dist);
+            if goes_front {
+                queue.push_front(other);
+            } else {
+                queue.push_back(other);
+            }
+        }
+    }
+
+    // Just to have 0-base indexing.
+    let shortcuts = shortcuts
+        .iter()
+        .map(|targets| targets.iter().map(|elt| elt - 1).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let mut queue = VecDeque::<usize>::new();
+    let mut distances = vec![None; shortcuts.len()];
+    let mut in_queue = vec![false; shortcuts.len()];
+    let mut sum = 0usize;
+    let mut count = 0usize;
+
+    distances[0] = Some(0);
+    in_queue[0] = true;
+    sum += 0;
+    count += 1;
+    queue.push_front(0);
+
+    while let Some(mut current) = queue.pop_front() {
+        // LLL: rotate pricier-than-average fronts to the back until the
+        // front is at or below the average.
+        while distances[current].map_or(false, |dist| dist > sum / count.max(1)) {
+            queue.push_back(current);
+            current = queue.pop_front().expect("just pushed a node back");
+        }
+
+        in_queue[current] = false;
+        sum -= distances[current].unwrap_or(0);
+        count -= 1;
+
+        let prev = (current > 0).then(|| current - 1);
+        let next = (current < shortcuts.len() - 1).then(|| current + 1);
+        let current_dist = distances[current].map_or(0, |current_dist| 1 + current_dist);
+
+        if let Some(prev) = prev {
+            update_and_push_if_better(
+                &mut queue,
+                &mut distances,
+                &mut in_queue,
+                &mut sum,
+                &mut count,
+                prev,
+                current_dist,
+            );
+        }
+        if let Some(next) = next {
+            update_and_push_if_better(
+                &mut queue,
+                &mut distances,
+                &mut in_queue,
+                &mut sum,
+                &mut count,
+                next,
+                current_dist,
+            );
+        }
+        for &shortcut in &shortcuts[current] {
+            update_and_push_if_better(
+                &mut queue,
+                &mut distances,
+                &mut in_queue,
+                &mut sum,
+                &mut count,
+                shortcut,
+                current_dist,
+            );
+        }
+    }
+
+    distances.into_iter().flatten().collect()
+}
+
+// Fringe Search over the same line+shortcuts graph as `solve`, but stops as
+// soon as `target`'s distance is known instead of filling in the whole
+// distance table — useful for a single query on large inputs.
+//
+// Nodes are kept in two deques: `now` is the current search fringe, `later`
+// holds nodes deferred this pass because their `f = g + h` exceeded
+// `flimit`. Each pass drains `now`, relaxing neighbors whose improved
+// distance gets pushed to the front (so cheap nodes are retried before
+// anything deferred), until `now` is empty; `flimit` is then raised to the
+// smallest deferred `f` and `later` becomes the new `now`.
+//
+// `h` stays 0: any bound derived from index distance alone could
+// overestimate once shortcuts are in play (a shortcut can close the gap to
+// `target` in fewer steps than walking the line), so 0 is the only bound
+// that's admissible without knowing more about this graph's shortcuts.
+//
+// Returns `None` if `target` is out of range or unreachable: `next_flimit`
+// staying at its `usize::MAX` sentinel for a whole pass means nothing was
+// deferred to `later`, i.e. the fringe has genuinely run dry without ever
+// expanding `target`, so there's nothing left to swap in and the search
+// stops instead of spinning on two empty deques forever.
+fn shortest_distance(shortcuts: &[Vec<usize>], target: usize) -> Option<usize> {
+    // If we found a better distance, update it and push the node to the
+    // front of the fringe so it's tried again before anything deferred.
+    fn relax(now: &mut VecDeque<usize>, g: &mut [usize], other: usize, current_dist: usize) {
+        if current_dist < g[other] {
+            g[other] = current_dist;
+            now.push_front(other);
+        }
+    }
+
+    if target == 0 || target > shortcuts.len() {
+        return None;
+    }
+
+    // Just to have 0-base indexing.
+    let shortcuts = shortcuts
+        .iter()
+        .map(|targets| targets.iter().map(|elt| elt - 1).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let target = target - 1;
+
+    let mut g = vec![usize::MAX; shortcuts.len()];
+    g[0] = 0;
+
+    let mut now = VecDeque::<usize>::new();
+    let mut later = VecDeque::<usize>::new();
+    now.push_front(0);
+    let mut flimit = 0usize;
+
+    loop {
+        let mut next_flimit = usize::MAX;
+
+        while let Some(current) = now.pop_front() {
+            if current == target {
+                return Some(g[current]);
+            }
+
+            let f = g[current]; // h is always 0, see above.
+            if f > flimit {
+                next_flimit = next_flimit.min(f);
+                later.push_back(current);
+                continue;
+            }
+
+            let prev = (current > 0).then(|| current - 1);
+            let next = (current < shortcuts.len() - 1).then(|| current + 1);
+            let current_dist = g[current] + 1;
+
+            if let Some(prev) = prev {
+                relax(&mut now, &mut g, prev, current_dist);
+            }
+            if let Some(next) = next {
+                relax(&mut now, &mut g, next, current_dist);
+            }
+            for &shortcut in &shortcuts[current] {
+                relax(&mut now, &mut g, shortcut, current_dist);
+            }
+        }
+
+        if next_flimit == usize::MAX {
+            return None; // Fringe ran dry: target is unreachable.
+        }
+        flimit = next_flimit;
+        std::mem::swap(&mut now, &mut later);
+    }
+}
+
+// Selects which solver `main` runs over the parsed graph, picked with an
+// optional `--mode=` CLI flag (see `parse_mode`). Defaults to `Distances`,
+// the original full distance table.
+enum Mode {
+    Distances,
+    Paths,
+    Weighted {
+        step_cost: usize,
+        shortcut_cost: usize,
+    },
+    SlfLll,
+    Fringe {
+        target: usize,
+    },
+}
+
+// Parses the `--mode=...` flag out of the program's CLI arguments (see
+// `std::env::args().skip(1)`), falling back to `Mode::Distances` if it's
+// absent or unrecognized. Recognized forms:
+// - `--mode=distances` (default): full table via `solve`.
+// - `--mode=paths`: full distance+path table via `solve_with_paths`.
+// - `--mode=weighted:<step_cost>,<shortcut_cost>`: `solve_weighted`.
+// - `--mode=slf-lll`: full table via `solve_slf_lll`.
+// - `--mode=fringe:<target>`: single-target query via `shortest_distance`.
+fn parse_mode<I: IntoIterator<Item = String>>(args: I) -> Mode {
+    let Some(mode) = args
+        .into_iter()
+        .find_map(|arg| arg.strip_prefix("--mode=").map(str::to_owned))
+    else {
+        return Mode::Distances;
+    };
+
+    match mode.split_once(':') {
+        Some(("weighted", costs)) => {
+            let (step_cost, shortcut_cost) = costs.split_once(',').expect("no issue");
+            Mode::Weighted {
+                step_cost: step_cost.parse().expect("no issue"),
+                shortcut_cost: shortcut_cost.parse().expect("no issue"),
+            }
+        }
+        Some(("fringe", target)) => Mode::Fringe {
+            target: target.parse().expect("no issue"),
+        },
+        _ if mode == "paths" => Mode::Paths,
+        _ if mode == "slf-lll" => Mode::SlfLll,
+        _ => Mode::Distances,
+    }
+}
+
+fn main() -> AnyResult<()> {
+    let mode = parse_mode(std::env::args().skip(1));
+
+    let mut lines = std::io::stdin().lock().lines();
+    let node_count = lines
+        .next()
+        .expect("missing header line")
+        .expect("no issue")
+        .trim()
+        .parse::<usize>()
+        .expect("no issue");
+    let lines = lines.map(|line| line.expect("no issue"));
+    let shortcuts = parse_graph(node_count, lines);
+
+    match mode {
+        Mode::Distances => {
+            for elt in solve(&shortcuts) {
+                print!("{} ", elt);
+            }
+            println!();
+        }
+        Mode::Paths => {
+            for (distance, path, moves) in solve_with_paths(&shortcuts) {
+                let path = path
+                    .iter()
+                    .map(|node| (node + 1).to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                println!("{distance}: {path} ({moves:?})");
+            }
+        }
+        Mode::Weighted {
+            step_cost,
+            shortcut_cost,
+        } => {
+            for elt in solve_weighted(&shortcuts, step_cost, shortcut_cost) {
+                print!("{} ", elt);
+            }
+            println!();
+        }
+        Mode::SlfLll => {
+            for elt in solve_slf_lll(&shortcuts) {
+                print!("{} ", elt);
+            }
+            println!();
+        }
+        Mode::Fringe { target } => match shortest_distance(&shortcuts, target) {
+            Some(distance) => println!("{distance}"),
+            None => println!("unreachable"),
+        },
     }
-    println!();
 
     Ok(())
 }
@@ -127,7 +647,7 @@ mod tests {
     // No shortcuts at all, linear score.
     #[test]
     fn test_identity() {
-        let res = solve(&vec![1, 2, 3, 4, 5]).collect::<Vec<_>>();
+        let res = solve(&[vec![1], vec![2], vec![3], vec![4], vec![5]]).collect::<Vec<_>>();
         assert_eq!(res, vec![0, 1, 2, 3, 4]);
     }
 
@@ -137,7 +657,7 @@ mod tests {
     // Non useful shortcut, score would be the same without.
     #[test]
     fn test_non_useful_shortcuts() {
-        let res = solve(&vec![2, 2, 3]).collect::<Vec<_>>();
+        let res = solve(&[vec![2], vec![2], vec![3]]).collect::<Vec<_>>();
         assert_eq!(res, vec![0, 1, 2]);
     }
 
@@ -147,7 +667,7 @@ mod tests {
     // Simple shortcut.
     #[test]
     fn test_basic_example() {
-        let res = solve(&vec![3, 2, 3]).collect::<Vec<_>>();
+        let res = solve(&[vec![3], vec![2], vec![3]]).collect::<Vec<_>>();
         assert_eq!(res, vec![0, 1, 1]);
     }
 
@@ -159,7 +679,7 @@ mod tests {
     // Simply apply all shortcuts to reduce dist.
     #[test]
     fn test_one_shortcut_groups() {
-        let res = solve(&vec![4, 4, 4, 4]).collect::<Vec<_>>();
+        let res = solve(&[vec![4], vec![4], vec![4], vec![4]]).collect::<Vec<_>>();
         assert_eq!(res, vec![0, 1, 2, 1]);
     }
 
@@ -172,7 +692,16 @@ mod tests {
     // still working as intended.
     #[test]
     fn test_two_shortcut_groups() {
-        let res = solve(&vec![4, 4, 4, 4, 7, 7, 7]).collect::<Vec<_>>();
+        let res = solve(&[
+            vec![4],
+            vec![4],
+            vec![4],
+            vec![4],
+            vec![7],
+            vec![7],
+            vec![7],
+        ])
+        .collect::<Vec<_>>();
         assert_eq!(res, vec![0, 1, 2, 1, 2, 3, 3]);
     }
 
@@ -184,7 +713,7 @@ mod tests {
     // It means it's 2 dist and not 3.
     #[test]
     fn test_can_go_backward() {
-        let res = solve(&vec![5, 2, 3, 4, 5]).collect::<Vec<_>>();
+        let res = solve(&[vec![5], vec![2], vec![3], vec![4], vec![5]]).collect::<Vec<_>>();
         assert_eq!(res, vec![0, 1, 2, 2, 1]);
     }
 
@@ -197,7 +726,189 @@ mod tests {
     // backward shortcut.
     #[test]
     fn test_main_example() {
-        let res = solve(&vec![7, 4, 4, 4, 5, 6, 7]).collect::<Vec<_>>();
+        let res = solve(&[
+            vec![7],
+            vec![4],
+            vec![4],
+            vec![4],
+            vec![5],
+            vec![6],
+            vec![7],
+        ])
+        .collect::<Vec<_>>();
         assert_eq!(res, vec![0, 1, 2, 2, 3, 2, 1]);
     }
+
+    // 1 - 2 - 3 - 4
+    // |  \_____/\_/
+    //  \________/
+    //
+    // Node 1 now carries *two* shortcuts (to 3 and to 4), exercising the
+    // generalized adjacency list instead of the old single-shortcut model.
+    #[test]
+    fn test_multiple_shortcuts_per_node() {
+        let res = solve(&[vec![3, 4], vec![2], vec![3], vec![4]]).collect::<Vec<_>>();
+        assert_eq!(res, vec![0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_parse_graph() {
+        let shortcuts = parse_graph(
+            4,
+            ["1: 3 4", "2: 2", "3: 3", "4: 4"]
+                .into_iter()
+                .map(String::from),
+        );
+        assert_eq!(shortcuts, vec![vec![3, 4], vec![2], vec![3], vec![4]]);
+    }
+
+    // 1 - 2 - 3 - 4 - 5 - 6
+    //
+    // Nodes 5 and 6 have no shortcuts, so they have no `node:` line at all.
+    // `parse_graph` must still return 6 entries (the last two empty) rather
+    // than stopping at the highest `node` it actually saw a line for.
+    #[test]
+    fn test_parse_graph_trailing_nodes_without_shortcuts() {
+        let shortcuts = parse_graph(
+            6,
+            ["1: 6", "2: 3", "3: 4", "4: 5"]
+                .into_iter()
+                .map(String::from),
+        );
+        assert_eq!(
+            shortcuts,
+            vec![vec![6], vec![3], vec![4], vec![5], vec![], vec![]]
+        );
+    }
+
+    // 1 - 2 - 3 - 4 - 5
+    // \______________/
+    //
+    // Same layout as `test_can_go_backward`: node 4 (index 3) is reached
+    // backward from the shortcut endpoint, so its predecessor must be node 5
+    // (index 4), not node 3 (index 2).
+    #[test]
+    fn test_solve_with_paths_can_go_backward() {
+        let res = solve_with_paths(&[vec![5], vec![2], vec![3], vec![4], vec![5]]);
+        let distances = res.iter().map(|(dist, _, _)| *dist).collect::<Vec<_>>();
+        assert_eq!(distances, vec![0, 1, 2, 2, 1]);
+
+        let (distance, path, moves) = &res[3];
+        assert_eq!(*distance, 2);
+        assert_eq!(path, &vec![0, 4, 3]);
+        assert_eq!(moves, &vec![Move::Shortcut, Move::Left]);
+    }
+
+    // 1 - 2 - 3
+    // \______/
+    //
+    // Same layout as `test_basic_example`, but with equal step and shortcut
+    // costs, so the weighted solver should agree with the unit-cost one.
+    #[test]
+    fn test_solve_weighted_matches_unit_cost() {
+        let res = solve_weighted(&[vec![3], vec![2], vec![3]], 1, 1);
+        assert_eq!(res, vec![0, 1, 1]);
+    }
+
+    // 1 - 2 - 3
+    // \______/
+    //
+    // Same shortcut, but now it's an expensive tunnel: going through it costs
+    // more than just walking the two normal steps.
+    #[test]
+    fn test_solve_weighted_expensive_shortcut() {
+        let res = solve_weighted(&[vec![3], vec![2], vec![3]], 1, 5);
+        assert_eq!(res, vec![0, 1, 2]);
+    }
+
+    // Same layouts as the `solve` tests above: the SLF+LLL queue discipline
+    // only changes expansion order, so it must agree on every distance.
+    #[test]
+    fn test_solve_slf_lll_matches_solve() {
+        assert_eq!(
+            solve_slf_lll(&[vec![1], vec![2], vec![3], vec![4], vec![5]]),
+            vec![0, 1, 2, 3, 4]
+        );
+        assert_eq!(solve_slf_lll(&[vec![3], vec![2], vec![3]]), vec![0, 1, 1]);
+        assert_eq!(
+            solve_slf_lll(&[vec![4], vec![4], vec![4], vec![4]]),
+            vec![0, 1, 2, 1]
+        );
+        assert_eq!(
+            solve_slf_lll(&[
+                vec![4],
+                vec![4],
+                vec![4],
+                vec![4],
+                vec![7],
+                vec![7],
+                vec![7]
+            ]),
+            vec![0, 1, 2, 1, 2, 3, 3]
+        );
+        assert_eq!(
+            solve_slf_lll(&[vec![5], vec![2], vec![3], vec![4], vec![5]]),
+            vec![0, 1, 2, 2, 1]
+        );
+        assert_eq!(
+            solve_slf_lll(&[
+                vec![7],
+                vec![4],
+                vec![4],
+                vec![4],
+                vec![5],
+                vec![6],
+                vec![7]
+            ]),
+            vec![0, 1, 2, 2, 3, 2, 1]
+        );
+    }
+
+    // 1 - 2 - 3 - 4 - 5
+    // \______________/
+    //
+    // Same layout as `test_can_go_backward`: querying node 4 directly should
+    // stop the search as soon as its distance is known, without ever filling
+    // in the rest of the table.
+    #[test]
+    fn test_shortest_distance_can_go_backward() {
+        let shortcuts = [vec![5], vec![2], vec![3], vec![4], vec![5]];
+        assert_eq!(shortest_distance(&shortcuts, 4), Some(2));
+    }
+
+    // 1 - 2 - 3 - 4 - 5 - 6 - 7
+    // |   |  \__//           /
+    // |    \____/           /
+    //  \___________________/
+    //
+    // Same layout as `test_main_example`: check every target agrees with the
+    // full distance table computed by `solve`.
+    #[test]
+    fn test_shortest_distance_matches_solve() {
+        let shortcuts = [
+            vec![7],
+            vec![4],
+            vec![4],
+            vec![4],
+            vec![5],
+            vec![6],
+            vec![7],
+        ];
+        let expected = solve(&shortcuts).collect::<Vec<_>>();
+        for (target, &dist) in expected.iter().enumerate() {
+            assert_eq!(shortest_distance(&shortcuts, target + 1), Some(dist));
+        }
+    }
+
+    // Querying a target index beyond the graph's size, or 0 (there's no
+    // node 0, indices are 1-based), must report `None` rather than hang:
+    // since it's never expanded, nothing is ever deferred to `later` either,
+    // so the search has to notice the fringe ran dry instead of spinning on
+    // two permanently empty deques.
+    #[test]
+    fn test_shortest_distance_out_of_range_target() {
+        let shortcuts = [vec![3], vec![2], vec![3]];
+        assert_eq!(shortest_distance(&shortcuts, 4), None);
+        assert_eq!(shortest_distance(&shortcuts, 0), None);
+    }
 }